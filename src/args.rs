@@ -19,14 +19,67 @@ pub enum PngMeArgs {
 pub struct EncodeArgs {
     pub file_path: PathBuf,
     pub chunk_type: String,
-    pub message: String,
+
+    /// The message to hide. Optional when `--file` or `--meta` supplies the
+    /// payload instead.
+    pub message: Option<String>,
+
+    /// Where to write the encoded PNG. Defaults to `output.png`. Named rather
+    /// than positional so it can't be confused with the also-optional `message`.
+    #[arg(short, long = "output", value_name = "FILE")]
     pub output_path: Option<PathBuf>,
+
+    /// Wrap the message in Reed-Solomon parity so `decode` can recover it even if
+    /// the chunk is corrupted (e.g. by an image editor stripping/recomputing CRCs).
+    #[arg(long)]
+    pub ecc: bool,
+
+    /// Read the payload as raw bytes from this file instead of from `message`,
+    /// so binary files (zips, keys, other images) can be hidden too.
+    #[arg(long)]
+    pub file: Option<PathBuf>,
+
+    /// Base64-encode the payload before it goes into the chunk, so the chunk data
+    /// stays printable-ASCII even when the payload has high bytes.
+    #[arg(long)]
+    pub base64: bool,
+
+    /// A `key=value` metadata record, repeatable. Values are typed automatically
+    /// (u64, RFC3339 datetime, or UTF-8 string). When given, builds a TLV metadata
+    /// payload instead of using `message`/`--file`.
+    #[arg(long = "meta", value_name = "KEY=VALUE")]
+    pub meta: Vec<String>,
 }
 
 #[derive(Args, Debug)]
 pub struct DecodeArgs {
     pub file_path: PathBuf,
     pub chunk_type: String,
+
+    /// Treat the chunk data as a Reed-Solomon-encoded payload and correct errors
+    /// before printing the message. Must match the `--ecc` flag used on encode.
+    #[arg(long)]
+    pub ecc: bool,
+
+    /// Scan chunk-by-chunk instead of parsing the whole file up front, logging
+    /// and skipping past any CRC mismatches instead of aborting.
+    #[arg(long)]
+    pub lenient: bool,
+
+    /// Write the recovered payload as raw bytes to this path instead of printing
+    /// it as a string. Required for payloads that are not valid UTF-8.
+    #[arg(long)]
+    pub out: Option<PathBuf>,
+
+    /// Strip a Base64 layer from the chunk data before using it. Must match the
+    /// `--base64` flag used on encode.
+    #[arg(long)]
+    pub base64: bool,
+
+    /// Parse the recovered payload as TLV metadata records and pretty-print the
+    /// typed fields instead of printing/writing raw bytes.
+    #[arg(long)]
+    pub meta: bool,
 }
 
 #[derive(Args, Debug)]
@@ -38,4 +91,9 @@ pub struct RemoveArgs {
 #[derive(Args, Debug)]
 pub struct PrintArgs {
     pub file_path: PathBuf,
+
+    /// Scan chunk-by-chunk instead of parsing the whole file up front, logging
+    /// and skipping past any CRC mismatches instead of aborting.
+    #[arg(long)]
+    pub lenient: bool,
 }
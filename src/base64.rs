@@ -0,0 +1,108 @@
+use std::fmt;
+
+use crate::Result;
+
+const ALPHABET: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// Encodes `data` as standard (RFC 4648) Base64 with `=` padding.
+pub fn encode(data: &[u8]) -> String {
+    let mut out = String::with_capacity(data.len().div_ceil(3) * 4);
+
+    for chunk in data.chunks(3) {
+        let b0 = chunk[0];
+        let b1 = chunk.get(1).copied().unwrap_or(0);
+        let b2 = chunk.get(2).copied().unwrap_or(0);
+
+        let c0 = b0 >> 2;
+        let c1 = ((b0 & 0b0000_0011) << 4) | (b1 >> 4);
+        let c2 = ((b1 & 0b0000_1111) << 2) | (b2 >> 6);
+        let c3 = b2 & 0b0011_1111;
+
+        out.push(ALPHABET[c0 as usize] as char);
+        out.push(ALPHABET[c1 as usize] as char);
+        out.push(if chunk.len() > 1 {
+            ALPHABET[c2 as usize] as char
+        } else {
+            '='
+        });
+        out.push(if chunk.len() > 2 {
+            ALPHABET[c3 as usize] as char
+        } else {
+            '='
+        });
+    }
+
+    out
+}
+
+/// Decodes a standard (RFC 4648) Base64 string, ignoring trailing `=` padding.
+pub fn decode(s: &str) -> Result<Vec<u8>> {
+    let bytes = s.trim_end_matches('=').as_bytes();
+    let mut out = Vec::with_capacity(bytes.len() * 3 / 4 + 3);
+
+    let mut buffer: u32 = 0;
+    let mut bits = 0u32;
+    for &byte in bytes {
+        let value = decode_char(byte)?;
+        buffer = (buffer << 6) | value as u32;
+        bits += 6;
+        if bits >= 8 {
+            bits -= 8;
+            out.push((buffer >> bits) as u8);
+        }
+    }
+
+    Ok(out)
+}
+
+fn decode_char(byte: u8) -> Result<u8> {
+    match byte {
+        b'A'..=b'Z' => Ok(byte - b'A'),
+        b'a'..=b'z' => Ok(byte - b'a' + 26),
+        b'0'..=b'9' => Ok(byte - b'0' + 52),
+        b'+' => Ok(62),
+        b'/' => Ok(63),
+        _ => Err(Box::new(Base64Error::InvalidCharacter(byte as char))),
+    }
+}
+
+/// Errors that can occur while decoding a Base64 string.
+#[derive(Debug)]
+pub enum Base64Error {
+    InvalidCharacter(char),
+}
+
+impl std::error::Error for Base64Error {}
+
+impl fmt::Display for Base64Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Base64Error::InvalidCharacter(c) => write!(f, "Invalid Base64 character: {:?}", c),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encode_known_vector() {
+        assert_eq!(encode(b"Rust"), "UnVzdA==");
+        assert_eq!(encode(b"This is where your secret message will be!").len() % 4, 0);
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let data = b"\x00\x01\xFFbinary data\xFE".to_vec();
+        let encoded = encode(&data);
+        let decoded = decode(&encoded).unwrap();
+        assert_eq!(decoded, data);
+    }
+
+    #[test]
+    fn test_decode_rejects_invalid_character() {
+        assert!(decode("not base64!").is_err());
+    }
+}
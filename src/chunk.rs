@@ -59,6 +59,7 @@ impl Chunk {
 
     /// Returns the data stored in this chunk as a `String`. This function will return an error
     /// if the stored data is not valid UTF-8.
+    #[allow(dead_code)]
     pub fn data_as_string(&self) -> Result<String> {
         let s = String::from_utf8(self.data.clone())?;
         Ok(s)
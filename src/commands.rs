@@ -5,9 +5,14 @@ use std::path::PathBuf;
 use std::str::FromStr;
 
 use crate::args::{DecodeArgs, EncodeArgs, PrintArgs, RemoveArgs};
+use crate::base64;
 use crate::chunk::Chunk;
 use crate::chunk_type::ChunkType;
+use crate::frame;
+use crate::metadata::{self, MetaRecords};
 use crate::png::Png;
+use crate::rs;
+use crate::stream::{ChunkEvent, StreamDecoder, StreamError};
 use crate::Result;
 
 /// Encodes a message into a PNG file and saves the result
@@ -15,12 +20,44 @@ pub fn encode(encode_args: EncodeArgs) -> Result<()> {
     let bytes = get_bytes_from_path(&encode_args.file_path);
     let mut png = Png::try_from(bytes.as_slice())?;
 
-    // Add the new chunk to the end of the file
+    let mut payload = if !encode_args.meta.is_empty() {
+        let mut records = MetaRecords::new();
+        for kv in &encode_args.meta {
+            let (key, value) = metadata::parse_kv(kv)?;
+            records.push(key, value);
+        }
+        records.to_bytes()
+    } else {
+        match &encode_args.file {
+            Some(payload_path) => get_bytes_from_path(payload_path),
+            None => encode_args
+                .message
+                .as_ref()
+                .expect("MESSAGE is required unless --file or --meta is given")
+                .as_bytes()
+                .to_vec(),
+        }
+    };
+
+    if encode_args.base64 {
+        payload = base64::encode(&payload).into_bytes();
+    }
+
+    let data = if encode_args.ecc {
+        rs::encode(&payload)
+    } else {
+        payload
+    };
+
+    let chunk_type = ChunkType::from_str(encode_args.chunk_type.as_str())?;
+
+    // Add the new chunk(s) to the end of the file. Large payloads are split
+    // across several same-typed chunks so no single chunk has to hold the
+    // whole message.
     let i_end = png.remove_chunk("IEND")?;
-    png.append_chunk(Chunk::new(
-        ChunkType::from_str(encode_args.chunk_type.as_str())?,
-        encode_args.message.as_bytes().to_vec(),
-    ));
+    for fragment_data in frame::fragment(&data) {
+        png.append_chunk(Chunk::new(chunk_type.clone(), fragment_data));
+    }
     png.append_chunk(i_end);
 
     let output_path = encode_args
@@ -35,14 +72,44 @@ pub fn encode(encode_args: EncodeArgs) -> Result<()> {
 
 /// Searches for a message hidden in a PNG file and prints the message if one is found
 pub fn decode(args: DecodeArgs) -> Result<()> {
-    let bytes = get_bytes_from_path(&args.file_path);
-    let png = Png::try_from(bytes.as_slice())?;
-
-    let target = png
-        .chunk_by_type(args.chunk_type.as_str())
+    let raw_fragments: Vec<Vec<u8>> = if args.lenient {
+        scan_for_chunks(&args.file_path, args.chunk_type.as_str())?
+    } else {
+        let bytes = get_bytes_from_path(&args.file_path);
+        let png = Png::try_from(bytes.as_slice())?;
+        png.chunks_by_type(args.chunk_type.as_str())
+            .iter()
+            .map(|chunk| chunk.data().to_vec())
+            .collect()
+    };
+
+    let raw_fragments = (!raw_fragments.is_empty())
+        .then_some(raw_fragments)
         .expect("Chunk not found");
 
-    println!("Hidden message: {}", target.data_as_string()?);
+    let mut payload = frame::reassemble(&raw_fragments)?;
+    if args.ecc {
+        payload = rs::decode(&payload)?;
+    }
+    if args.base64 {
+        payload = base64::decode(String::from_utf8(payload)?.trim())?;
+    }
+
+    if args.meta {
+        let records = MetaRecords::from_bytes(&payload)?;
+        for (key, value) in records.entries() {
+            println!("{}: {}", key, value);
+        }
+        return Ok(());
+    }
+
+    match &args.out {
+        Some(out_path) => {
+            fs::write(out_path, &payload)?;
+            println!("Message written to {}", out_path.display());
+        }
+        None => println!("Hidden message: {}", String::from_utf8(payload)?),
+    }
 
     Ok(())
 }
@@ -64,6 +131,11 @@ pub fn remove(args: RemoveArgs) -> Result<()> {
 
 /// Prints all of the chunks in a PNG file
 pub fn print_chunks(args: PrintArgs) -> Result<()> {
+    if args.lenient {
+        for_each_chunk(&args.file_path, |chunk| println!("{}", chunk))?;
+        return Ok(());
+    }
+
     let bytes = get_bytes_from_path(&args.file_path);
     let png = Png::try_from(bytes.as_slice())?;
 
@@ -80,3 +152,45 @@ fn get_bytes_from_path(path: &PathBuf) -> Vec<u8> {
     f.read_to_end(&mut buffer).expect("Unable to read file");
     buffer
 }
+
+/// Streams `path` chunk by chunk, calling `on_chunk` for every chunk whose CRC
+/// checks out. CRC mismatches are logged to stderr and skipped rather than
+/// aborting the scan, which lets this run against truncated or editor-mangled PNGs.
+fn for_each_chunk(path: &PathBuf, mut on_chunk: impl FnMut(&Chunk)) -> Result<()> {
+    let file = File::open(path)?;
+    let mut decoder = StreamDecoder::new(file);
+
+    loop {
+        match decoder.next_event() {
+            Ok(Some(ChunkEvent::Complete(chunk))) => on_chunk(&chunk),
+            Ok(Some(_)) => {}
+            Ok(None) => return Ok(()),
+            Err(err) => match err.downcast_ref::<StreamError>() {
+                Some(StreamError::CrcMismatch {
+                    chunk_type,
+                    expected,
+                    actual,
+                    recover,
+                }) => {
+                    eprintln!(
+                        "Warning: CRC mismatch in chunk {} (expected {} but got {}); skipping {} bytes, stream now at offset {}",
+                        chunk_type, expected, actual, recover, decoder.position()
+                    );
+                }
+                _ => return Err(err),
+            },
+        }
+    }
+}
+
+/// Lenient equivalent of `Png::chunks_by_type`: streams `path` chunk by chunk,
+/// skipping CRC mismatches, and returns the data of every chunk of `chunk_type` found.
+fn scan_for_chunks(path: &PathBuf, chunk_type: &str) -> Result<Vec<Vec<u8>>> {
+    let mut found = Vec::new();
+    for_each_chunk(path, |chunk| {
+        if chunk.chunk_type().to_string() == chunk_type {
+            found.push(chunk.data().to_vec());
+        }
+    })?;
+    Ok(found)
+}
@@ -0,0 +1,247 @@
+use std::convert::TryInto;
+use std::fmt;
+
+use crate::Result;
+
+/// Magic bytes identifying a multi-chunk fragment, written at the start of
+/// each fragment's chunk data ahead of the rest of the framing header.
+const MAGIC: [u8; 4] = *b"PmFr";
+
+/// Size of the framing header: magic (4) + total length (4) + sequence index (2)
+/// + fragment count (2).
+const HEADER_LEN: usize = 12;
+
+/// Payload bytes carried by each fragment, not counting the header. Keeps
+/// individual chunks a modest size for stealth and comfortably under the
+/// 2^31-byte PNG chunk length limit.
+pub const FRAGMENT_DATA_LEN: usize = 1024;
+
+/// Splits `payload` into chunk data, one entry per same-typed chunk that
+/// `encode` will append. Payloads that fit in a single chunk are returned
+/// as-is with no framing overhead, so the common case is byte-for-byte what
+/// it was before multi-chunk framing existed. Only payloads that need more
+/// than one fragment get the framing header (magic bytes, total reassembled
+/// length, sequence index, fragment count), mirroring chunked-transfer
+/// reassembly.
+pub fn fragment(payload: &[u8]) -> Vec<Vec<u8>> {
+    if payload.len() <= FRAGMENT_DATA_LEN {
+        return vec![payload.to_vec()];
+    }
+
+    let blocks: Vec<&[u8]> = payload.chunks(FRAGMENT_DATA_LEN).collect();
+    let count = blocks.len() as u16;
+    let total_len = payload.len() as u32;
+
+    blocks
+        .into_iter()
+        .enumerate()
+        .map(|(i, data)| {
+            let mut framed = Vec::with_capacity(HEADER_LEN + data.len());
+            framed.extend_from_slice(&MAGIC);
+            framed.extend_from_slice(&total_len.to_be_bytes());
+            framed.extend_from_slice(&(i as u16).to_be_bytes());
+            framed.extend_from_slice(&count.to_be_bytes());
+            framed.extend_from_slice(data);
+            framed
+        })
+        .collect()
+}
+
+struct Fragment {
+    total_len: u32,
+    seq: u16,
+    count: u16,
+    data: Vec<u8>,
+}
+
+fn parse_fragment(bytes: &[u8]) -> Result<Fragment> {
+    if bytes.len() < HEADER_LEN {
+        return Err(Box::new(FrameError::TooShort));
+    }
+
+    let (header, data) = bytes.split_at(HEADER_LEN);
+    if header[0..4] != MAGIC {
+        return Err(Box::new(FrameError::MissingMagic));
+    }
+
+    let total_len = u32::from_be_bytes(header[4..8].try_into()?);
+    let seq = u16::from_be_bytes(header[8..10].try_into()?);
+    let count = u16::from_be_bytes(header[10..12].try_into()?);
+
+    Ok(Fragment {
+        total_len,
+        seq,
+        count,
+        data: data.to_vec(),
+    })
+}
+
+/// Reassembles a payload from its chunk data. Chunks may be framed fragments
+/// (which may arrive in any order) or, for payloads that `fragment` wrote as
+/// a single unframed chunk, plain raw bytes with no header at all. A lone
+/// chunk missing the framing magic is treated as raw so chunks written before
+/// framing existed (or never needing it) still decode. Verifies that every
+/// sequence index in `0..count` is present exactly once and that the
+/// reassembled length matches the header before returning.
+pub fn reassemble(raw_fragments: &[Vec<u8>]) -> Result<Vec<u8>> {
+    if raw_fragments.is_empty() {
+        return Err(Box::new(FrameError::NoFragments));
+    }
+
+    if let [only] = raw_fragments {
+        if !only.starts_with(&MAGIC) {
+            return Ok(only.clone());
+        }
+    }
+
+    let parsed: Vec<Fragment> = raw_fragments
+        .iter()
+        .map(|bytes| parse_fragment(bytes))
+        .collect::<Result<Vec<_>>>()?;
+
+    let count = parsed[0].count;
+    let total_len = parsed[0].total_len;
+
+    let mut by_seq: Vec<Option<Vec<u8>>> = (0..count).map(|_| None).collect();
+    for frag in parsed {
+        if frag.count != count || frag.total_len != total_len {
+            return Err(Box::new(FrameError::InconsistentHeader));
+        }
+        if frag.seq >= count {
+            return Err(Box::new(FrameError::SequenceOutOfRange(frag.seq, count)));
+        }
+        if by_seq[frag.seq as usize].is_some() {
+            return Err(Box::new(FrameError::DuplicateFragment(frag.seq)));
+        }
+        by_seq[frag.seq as usize] = Some(frag.data);
+    }
+
+    let mut payload = Vec::with_capacity(total_len as usize);
+    for (seq, slot) in by_seq.into_iter().enumerate() {
+        match slot {
+            Some(data) => payload.extend(data),
+            None => return Err(Box::new(FrameError::MissingFragment(seq as u16, count))),
+        }
+    }
+
+    if payload.len() != total_len as usize {
+        return Err(Box::new(FrameError::LengthMismatch {
+            expected: total_len as usize,
+            actual: payload.len(),
+        }));
+    }
+
+    Ok(payload)
+}
+
+/// Errors that can occur while reassembling a multi-chunk payload.
+#[derive(Debug)]
+pub enum FrameError {
+    TooShort,
+    MissingMagic,
+    NoFragments,
+    InconsistentHeader,
+    SequenceOutOfRange(u16, u16),
+    DuplicateFragment(u16),
+    MissingFragment(u16, u16),
+    LengthMismatch { expected: usize, actual: usize },
+}
+
+impl std::error::Error for FrameError {}
+
+impl fmt::Display for FrameError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FrameError::TooShort => write!(
+                f,
+                "Fragment is shorter than the {}-byte framing header",
+                HEADER_LEN
+            ),
+            FrameError::MissingMagic => {
+                write!(f, "Fragment is missing the multi-chunk framing magic bytes")
+            }
+            FrameError::NoFragments => {
+                write!(f, "No fragments of the requested chunk type were found")
+            }
+            FrameError::InconsistentHeader => {
+                write!(f, "Fragments disagree on total length or fragment count")
+            }
+            FrameError::SequenceOutOfRange(seq, count) => write!(
+                f,
+                "Fragment sequence index {} is out of range for a {}-fragment message",
+                seq, count
+            ),
+            FrameError::DuplicateFragment(seq) => {
+                write!(f, "Duplicate fragment at sequence index {}", seq)
+            }
+            FrameError::MissingFragment(seq, count) => {
+                write!(f, "Missing fragment {} of {}", seq, count)
+            }
+            FrameError::LengthMismatch { expected, actual } => write!(
+                f,
+                "Reassembled payload is {} bytes but the header declared {}",
+                actual, expected
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip_single_fragment() {
+        let payload = b"This is where your secret message will be!".to_vec();
+        let fragments = fragment(&payload);
+        assert_eq!(fragments.len(), 1);
+        assert_eq!(reassemble(&fragments).unwrap(), payload);
+    }
+
+    #[test]
+    fn test_single_fragment_has_no_framing_overhead() {
+        let payload = b"short message".to_vec();
+        let fragments = fragment(&payload);
+        assert_eq!(fragments, vec![payload]);
+    }
+
+    #[test]
+    fn test_reassemble_falls_back_to_raw_for_legacy_unframed_chunk() {
+        let payload = b"a chunk written before framing existed".to_vec();
+        let fragments = vec![payload.clone()];
+        assert_eq!(reassemble(&fragments).unwrap(), payload);
+    }
+
+    #[test]
+    fn test_round_trip_multiple_fragments() {
+        let payload: Vec<u8> = (0..10_000).map(|i| (i % 256) as u8).collect();
+        let fragments = fragment(&payload);
+        assert!(fragments.len() > 1);
+        assert_eq!(reassemble(&fragments).unwrap(), payload);
+    }
+
+    #[test]
+    fn test_reassemble_out_of_order() {
+        let payload: Vec<u8> = (0..5000).map(|i| (i % 256) as u8).collect();
+        let mut fragments = fragment(&payload);
+        fragments.reverse();
+        assert_eq!(reassemble(&fragments).unwrap(), payload);
+    }
+
+    #[test]
+    fn test_reassemble_rejects_missing_fragment() {
+        let payload: Vec<u8> = (0..5000).map(|i| (i % 256) as u8).collect();
+        let mut fragments = fragment(&payload);
+        fragments.remove(1);
+        assert!(reassemble(&fragments).is_err());
+    }
+
+    #[test]
+    fn test_reassemble_rejects_duplicate_fragment() {
+        let payload: Vec<u8> = (0..5000).map(|i| (i % 256) as u8).collect();
+        let mut fragments = fragment(&payload);
+        let dup = fragments[0].clone();
+        fragments.push(dup);
+        assert!(reassemble(&fragments).is_err());
+    }
+}
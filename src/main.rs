@@ -1,8 +1,13 @@
 mod args;
+mod base64;
 mod chunk;
 mod chunk_type;
 mod commands;
+mod frame;
+mod metadata;
 mod png;
+mod rs;
+mod stream;
 
 use args::{Cli, PngMeArgs};
 use clap::Parser;
@@ -0,0 +1,299 @@
+use std::fmt;
+
+use crate::Result;
+
+const TAG_UTF8: u8 = 1;
+const TAG_BYTES: u8 = 2;
+const TAG_U64: u8 = 3;
+const TAG_DATETIME: u8 = 4;
+
+/// A single typed metadata value, as carried by one TLV record.
+#[derive(Debug, Clone, PartialEq)]
+pub enum MetaValue {
+    Utf8(String),
+    Bytes(Vec<u8>),
+    U64(u64),
+    /// An RFC3339-style timestamp, stored as text rather than parsed. Only
+    /// `parse_kv` checks its shape; `from_bytes` accepts any UTF-8 text here.
+    DateTime(String),
+}
+
+impl fmt::Display for MetaValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MetaValue::Utf8(s) => write!(f, "{}", s),
+            MetaValue::Bytes(b) => write!(f, "<{} bytes>", b.len()),
+            MetaValue::U64(n) => write!(f, "{}", n),
+            MetaValue::DateTime(s) => write!(f, "{}", s),
+        }
+    }
+}
+
+/// An ordered list of `key = value` metadata records, stored in a chunk as
+/// alternating `[tag: u8][len: varint][value: len bytes]` records (a key record
+/// followed by its typed value record), inspired by DER/ASN.1 TLV encoding.
+#[derive(Debug, Default)]
+pub struct MetaRecords {
+    entries: Vec<(String, MetaValue)>,
+}
+
+impl MetaRecords {
+    pub fn new() -> Self {
+        Self { entries: Vec::new() }
+    }
+
+    pub fn push(&mut self, key: String, value: MetaValue) {
+        self.entries.push((key, value));
+    }
+
+    pub fn entries(&self) -> &[(String, MetaValue)] {
+        &self.entries
+    }
+
+    /// Encodes every `key = value` pair as two consecutive TLV records.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut out = Vec::new();
+        for (key, value) in &self.entries {
+            write_record(&mut out, TAG_UTF8, key.as_bytes());
+            match value {
+                MetaValue::Utf8(s) => write_record(&mut out, TAG_UTF8, s.as_bytes()),
+                MetaValue::Bytes(b) => write_record(&mut out, TAG_BYTES, b),
+                MetaValue::U64(n) => write_record(&mut out, TAG_U64, &n.to_be_bytes()),
+                MetaValue::DateTime(s) => write_record(&mut out, TAG_DATETIME, s.as_bytes()),
+            }
+        }
+        out
+    }
+
+    /// Parses `bytes` back into records. Unknown tags are skipped rather than
+    /// treated as fatal, so newer writers can add tags without breaking older
+    /// readers. Any record whose declared length runs past the end of the
+    /// buffer stops parsing rather than panicking.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        let mut entries = Vec::new();
+        let mut pos = 0;
+
+        while pos < bytes.len() {
+            let (key_tag, key_bytes, key_consumed) = match read_record(bytes, pos) {
+                Some(record) => record,
+                None => break,
+            };
+            pos += key_consumed;
+
+            if key_tag != TAG_UTF8 {
+                continue;
+            }
+            let key = match String::from_utf8(key_bytes.to_vec()) {
+                Ok(key) => key,
+                Err(_) => continue,
+            };
+
+            let (value_tag, value_bytes, value_consumed) = match read_record(bytes, pos) {
+                Some(record) => record,
+                None => break,
+            };
+            pos += value_consumed;
+
+            let value = match value_tag {
+                TAG_UTF8 => String::from_utf8(value_bytes.to_vec()).ok().map(MetaValue::Utf8),
+                TAG_BYTES => Some(MetaValue::Bytes(value_bytes.to_vec())),
+                TAG_U64 => <[u8; 8]>::try_from(value_bytes)
+                    .ok()
+                    .map(|b| MetaValue::U64(u64::from_be_bytes(b))),
+                TAG_DATETIME => String::from_utf8(value_bytes.to_vec())
+                    .ok()
+                    .map(MetaValue::DateTime),
+                _ => None,
+            };
+
+            if let Some(value) = value {
+                entries.push((key, value));
+            }
+        }
+
+        Ok(Self { entries })
+    }
+}
+
+fn write_varint(out: &mut Vec<u8>, mut value: usize) {
+    loop {
+        let mut byte = (value & 0x7F) as u8;
+        value >>= 7;
+        if value != 0 {
+            byte |= 0x80;
+        }
+        out.push(byte);
+        if value == 0 {
+            break;
+        }
+    }
+}
+
+fn read_varint(bytes: &[u8], pos: usize) -> Option<(usize, usize)> {
+    let mut value: usize = 0;
+    let mut shift = 0u32;
+    let mut i = pos;
+
+    loop {
+        let byte = *bytes.get(i)?;
+        value |= ((byte & 0x7F) as usize) << shift;
+        i += 1;
+        if byte & 0x80 == 0 {
+            break;
+        }
+        shift += 7;
+        if shift >= usize::BITS {
+            return None;
+        }
+    }
+
+    Some((value, i - pos))
+}
+
+fn write_record(out: &mut Vec<u8>, tag: u8, value: &[u8]) {
+    out.push(tag);
+    write_varint(out, value.len());
+    out.extend_from_slice(value);
+}
+
+fn read_record(bytes: &[u8], pos: usize) -> Option<(u8, &[u8], usize)> {
+    let tag = *bytes.get(pos)?;
+    let (len, varint_len) = read_varint(bytes, pos + 1)?;
+    let value_start = pos + 1 + varint_len;
+    let value_end = value_start.checked_add(len)?;
+
+    if value_end > bytes.len() {
+        return None;
+    }
+
+    Some((tag, &bytes[value_start..value_end], 1 + varint_len + len))
+}
+
+/// Parses a CLI `key=value` string into a typed record: a `u64` if the value
+/// parses as one, an RFC3339-style datetime if it looks like one, otherwise a
+/// plain UTF-8 string.
+pub fn parse_kv(input: &str) -> Result<(String, MetaValue)> {
+    let (key, value) = input
+        .split_once('=')
+        .ok_or_else(|| MetaError::InvalidKeyValue(input.to_string()))?;
+
+    let value = if let Ok(n) = value.parse::<u64>() {
+        MetaValue::U64(n)
+    } else if looks_like_rfc3339(value) {
+        MetaValue::DateTime(value.to_string())
+    } else {
+        MetaValue::Utf8(value.to_string())
+    };
+
+    Ok((key.to_string(), value))
+}
+
+/// Loose structural check for `YYYY-MM-DDTHH:MM:SS` followed by a `Z` or a
+/// `+hh:mm`/`-hh:mm` offset; does not validate calendar correctness.
+fn looks_like_rfc3339(s: &str) -> bool {
+    let bytes = s.as_bytes();
+    if bytes.len() < 20 {
+        return false;
+    }
+
+    let digit = |i: usize| bytes.get(i).is_some_and(u8::is_ascii_digit);
+    let structure = digit(0) && digit(1) && digit(2) && digit(3)
+        && bytes[4] == b'-'
+        && digit(5) && digit(6)
+        && bytes[7] == b'-'
+        && digit(8) && digit(9)
+        && bytes[10] == b'T'
+        && digit(11) && digit(12)
+        && bytes[13] == b':'
+        && digit(14) && digit(15)
+        && bytes[16] == b':'
+        && digit(17) && digit(18);
+
+    structure && (s.ends_with('Z') || s[19..].contains('+') || s[19..].contains('-'))
+}
+
+#[derive(Debug)]
+pub enum MetaError {
+    InvalidKeyValue(String),
+}
+
+impl std::error::Error for MetaError {}
+
+impl fmt::Display for MetaError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MetaError::InvalidKeyValue(input) => {
+                write!(f, "Expected `key=value` but got `{}`", input)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip() {
+        let mut records = MetaRecords::new();
+        records.push("author".to_string(), MetaValue::Utf8("Ferris".to_string()));
+        records.push("views".to_string(), MetaValue::U64(42));
+        records.push(
+            "created".to_string(),
+            MetaValue::DateTime("2024-01-02T03:04:05Z".to_string()),
+        );
+        records.push("thumbnail".to_string(), MetaValue::Bytes(vec![1, 2, 3]));
+
+        let bytes = records.to_bytes();
+        let parsed = MetaRecords::from_bytes(&bytes).unwrap();
+
+        assert_eq!(parsed.entries(), records.entries());
+    }
+
+    #[test]
+    fn test_unknown_tag_is_skipped_not_fatal() {
+        let mut records = MetaRecords::new();
+        records.push("a".to_string(), MetaValue::U64(1));
+
+        let mut bytes = records.to_bytes();
+        // Append a record with an unrecognized tag (99); it should be skipped.
+        write_record(&mut bytes, 99, b"future extension");
+        write_record(&mut bytes, TAG_UTF8, b"b");
+        write_record(&mut bytes, TAG_UTF8, b"after-unknown");
+
+        let parsed = MetaRecords::from_bytes(&bytes).unwrap();
+        assert_eq!(
+            parsed.entries(),
+            &[
+                ("a".to_string(), MetaValue::U64(1)),
+                ("b".to_string(), MetaValue::Utf8("after-unknown".to_string())),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_truncated_length_does_not_panic() {
+        let bytes = vec![TAG_UTF8, 0xC8, 0x01]; // varint 200: declares 200 bytes of value but none follow
+        assert!(MetaRecords::from_bytes(&bytes).unwrap().entries().is_empty());
+    }
+
+    #[test]
+    fn test_parse_kv_infers_types() {
+        assert_eq!(
+            parse_kv("views=42").unwrap(),
+            ("views".to_string(), MetaValue::U64(42))
+        );
+        assert_eq!(
+            parse_kv("created=2024-01-02T03:04:05Z").unwrap(),
+            (
+                "created".to_string(),
+                MetaValue::DateTime("2024-01-02T03:04:05Z".to_string())
+            )
+        );
+        assert_eq!(
+            parse_kv("author=Ferris").unwrap(),
+            ("author".to_string(), MetaValue::Utf8("Ferris".to_string()))
+        );
+        assert!(parse_kv("no-equals-sign").is_err());
+    }
+}
@@ -0,0 +1,248 @@
+use std::convert::TryFrom;
+use std::fmt;
+
+use crate::chunk::Chunk;
+use crate::{Error, Result};
+
+/// A PNG container, made up of a fixed 8-byte signature followed by a sequence of `Chunk`s.
+/// See the PNG Spec for more details http://www.libpng.org/pub/png/spec/1.2/PNG-Structure.html
+#[derive(Debug)]
+pub struct Png {
+    chunks: Vec<Chunk>,
+}
+
+impl Png {
+    pub const STANDARD_HEADER: [u8; 8] = [137, 80, 78, 71, 13, 10, 26, 10];
+
+    /// Creates a `Png` from a list of chunks, in the order given.
+    pub fn from_chunks(chunks: Vec<Chunk>) -> Self {
+        Self { chunks }
+    }
+
+    /// Appends a chunk to the end of this `Png`'s chunk list.
+    pub fn append_chunk(&mut self, chunk: Chunk) {
+        self.chunks.push(chunk);
+    }
+
+    /// Removes the first chunk with the given chunk type, returning it if found.
+    pub fn remove_chunk(&mut self, chunk_type: &str) -> Result<Chunk> {
+        let index = self
+            .chunks
+            .iter()
+            .position(|chunk| chunk.chunk_type().to_string() == chunk_type)
+            .ok_or(PngError::ChunkNotFound)?;
+
+        Ok(self.chunks.remove(index))
+    }
+
+    /// The standard 8-byte PNG header.
+    #[allow(dead_code)]
+    pub fn header(&self) -> &[u8; 8] {
+        &Self::STANDARD_HEADER
+    }
+
+    /// The chunks contained in this `Png`, in file order.
+    pub fn chunks(&self) -> &[Chunk] {
+        &self.chunks
+    }
+
+    /// Returns the first chunk matching the given chunk type, if any.
+    #[allow(dead_code)]
+    pub fn chunk_by_type(&self, chunk_type: &str) -> Option<&Chunk> {
+        self.chunks
+            .iter()
+            .find(|chunk| chunk.chunk_type().to_string() == chunk_type)
+    }
+
+    /// Returns every chunk matching the given chunk type, in file order.
+    pub fn chunks_by_type(&self, chunk_type: &str) -> Vec<&Chunk> {
+        self.chunks
+            .iter()
+            .filter(|chunk| chunk.chunk_type().to_string() == chunk_type)
+            .collect()
+    }
+
+    /// Encodes this `Png` as bytes described by the PNG spec: the 8-byte header
+    /// followed by each chunk's byte representation, in order.
+    pub fn as_bytes(&self) -> Vec<u8> {
+        Self::STANDARD_HEADER
+            .iter()
+            .chain(self.chunks.iter().flat_map(|chunk| chunk.as_bytes()).collect::<Vec<u8>>().iter())
+            .copied()
+            .collect()
+    }
+}
+
+impl TryFrom<&[u8]> for Png {
+    type Error = Error;
+
+    fn try_from(bytes: &[u8]) -> Result<Self> {
+        if bytes.len() < Self::STANDARD_HEADER.len() {
+            return Err(Box::new(PngError::InputTooShort));
+        }
+
+        let (header, mut bytes) = bytes.split_at(Self::STANDARD_HEADER.len());
+        if header != Self::STANDARD_HEADER {
+            return Err(Box::new(PngError::InvalidHeader));
+        }
+
+        let mut chunks = Vec::new();
+        while !bytes.is_empty() {
+            let chunk = Chunk::try_from(bytes)?;
+            let chunk_len = Chunk::METADATA_BYTES + chunk.length() as usize;
+            bytes = &bytes[chunk_len..];
+            chunks.push(chunk);
+        }
+
+        Ok(Self::from_chunks(chunks))
+    }
+}
+
+impl fmt::Display for Png {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Png {{",)?;
+        for chunk in &self.chunks {
+            writeln!(f, "  {}", chunk)?;
+        }
+        writeln!(f, "}}",)?;
+        Ok(())
+    }
+}
+
+/// Errors that can occur when constructing a `Png`
+#[derive(Debug)]
+pub enum PngError {
+    InputTooShort,
+    InvalidHeader,
+    ChunkNotFound,
+}
+
+impl std::error::Error for PngError {}
+
+impl fmt::Display for PngError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PngError::InputTooShort => {
+                write!(f, "At least 8 bytes must be supplied to construct a Png")
+            }
+            PngError::InvalidHeader => write!(f, "Input does not start with the PNG header"),
+            PngError::ChunkNotFound => write!(f, "No chunk with the given type was found"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chunk_type::ChunkType;
+    use std::str::FromStr;
+
+    fn testing_chunks() -> Vec<Chunk> {
+        vec![
+            chunk_from_strings("FrSt", "I am the first chunk").unwrap(),
+            chunk_from_strings("miDl", "I am another chunk").unwrap(),
+            chunk_from_strings("LASt", "I am the last chunk").unwrap(),
+        ]
+    }
+
+    fn chunk_from_strings(chunk_type: &str, data: &str) -> Result<Chunk> {
+        let chunk_type = ChunkType::from_str(chunk_type)?;
+        let data: Vec<u8> = data.bytes().collect();
+
+        Ok(Chunk::new(chunk_type, data))
+    }
+
+    fn testing_png() -> Png {
+        let chunks = testing_chunks();
+        Png::from_chunks(chunks)
+    }
+
+    #[test]
+    fn test_from_chunks() {
+        let chunks = testing_chunks();
+        let png = Png::from_chunks(chunks);
+
+        assert_eq!(png.chunks().len(), 3);
+    }
+
+    #[test]
+    fn test_valid_from_bytes() {
+        let chunk_bytes: Vec<u8> = testing_chunks()
+            .into_iter()
+            .flat_map(|chunk| chunk.as_bytes())
+            .collect();
+
+        let bytes: Vec<u8> = Png::STANDARD_HEADER
+            .iter()
+            .chain(chunk_bytes.iter())
+            .copied()
+            .collect();
+
+        let png = Png::try_from(bytes.as_ref()).unwrap();
+
+        assert_eq!(png.chunks().len(), 3);
+    }
+
+    #[test]
+    fn test_invalid_header() {
+        let chunk_bytes: Vec<u8> = testing_chunks()
+            .into_iter()
+            .flat_map(|chunk| chunk.as_bytes())
+            .collect();
+
+        let bytes: Vec<u8> = [13, 80, 78, 71, 13, 10, 26, 10]
+            .iter()
+            .chain(chunk_bytes.iter())
+            .copied()
+            .collect();
+
+        let png = Png::try_from(bytes.as_ref());
+
+        assert!(png.is_err());
+    }
+
+    #[test]
+    fn test_append_chunk() {
+        let mut png = testing_png();
+        png.append_chunk(chunk_from_strings("TeSt", "Message").unwrap());
+
+        assert_eq!(png.chunk_by_type("TeSt").unwrap().data_as_string().unwrap(), "Message");
+    }
+
+    #[test]
+    fn test_remove_chunk() {
+        let mut png = testing_png();
+        png.append_chunk(chunk_from_strings("TeSt", "Message").unwrap());
+        png.remove_chunk("TeSt").unwrap();
+        let chunk = png.chunk_by_type("TeSt");
+
+        assert!(chunk.is_none());
+    }
+
+    #[test]
+    fn test_as_bytes() {
+        let png = testing_png();
+        let bytes = png.as_bytes();
+
+        let round_tripped = Png::try_from(bytes.as_ref()).unwrap();
+        assert_eq!(round_tripped.chunks().len(), png.chunks().len());
+    }
+
+    #[test]
+    fn test_png_trait_impls() {
+        let chunk_bytes: Vec<u8> = testing_chunks()
+            .into_iter()
+            .flat_map(|chunk| chunk.as_bytes())
+            .collect();
+
+        let bytes: Vec<u8> = Png::STANDARD_HEADER
+            .iter()
+            .chain(chunk_bytes.iter())
+            .copied()
+            .collect();
+
+        let png: Png = TryFrom::try_from(bytes.as_ref()).unwrap();
+
+        let _png_string = format!("{}", png);
+    }
+}
@@ -0,0 +1,410 @@
+use std::fmt;
+
+use crate::Result;
+
+/// Primitive polynomial used to build GF(2^8): x^8 + x^4 + x^3 + x^2 + 1.
+const PRIMITIVE_POLY: u16 = 0x11D;
+
+/// Number of parity bytes appended to each codeword by the `--ecc` mode.
+/// Corrects up to `PARITY_LEN / 2` byte errors per block.
+pub const PARITY_LEN: usize = 32;
+
+/// Codeword size in bytes (`DATA_LEN` data bytes + `PARITY_LEN` parity bytes).
+const CODEWORD_LEN: usize = 255;
+
+/// Maximum number of data bytes carried by a single codeword.
+pub const DATA_LEN: usize = CODEWORD_LEN - PARITY_LEN;
+
+/// Size of the header prepended to an ECC-wrapped payload, giving the
+/// original (unpadded) length so the final block's zero padding can be stripped.
+const HEADER_LEN: usize = 4;
+
+/// Exponent/log tables for arithmetic in GF(2^8), built from `PRIMITIVE_POLY`.
+struct Gf256 {
+    exp: [u8; 512],
+    log: [u8; 256],
+}
+
+impl Gf256 {
+    fn new() -> Self {
+        let mut exp = [0u8; 512];
+        let mut log = [0u8; 256];
+
+        let mut x: u16 = 1;
+        for (i, slot) in exp.iter_mut().enumerate().take(255) {
+            *slot = x as u8;
+            log[x as usize] = i as u8;
+            x <<= 1;
+            if x & 0x100 != 0 {
+                x ^= PRIMITIVE_POLY;
+            }
+        }
+        for i in 255..512 {
+            exp[i] = exp[i - 255];
+        }
+
+        Self { exp, log }
+    }
+
+    fn mul(&self, a: u8, b: u8) -> u8 {
+        if a == 0 || b == 0 {
+            return 0;
+        }
+        self.exp[self.log[a as usize] as usize + self.log[b as usize] as usize]
+    }
+
+    fn div(&self, a: u8, b: u8) -> u8 {
+        debug_assert!(b != 0, "division by zero in GF(256)");
+        if a == 0 {
+            return 0;
+        }
+        self.exp[(self.log[a as usize] as usize + 255 - self.log[b as usize] as usize) % 255]
+    }
+
+    fn pow(&self, a: u8, power: usize) -> u8 {
+        if a == 0 {
+            return 0;
+        }
+        self.exp[(self.log[a as usize] as usize * power) % 255]
+    }
+
+    fn inv(&self, a: u8) -> u8 {
+        self.exp[255 - self.log[a as usize] as usize]
+    }
+}
+
+/// Multiplies two polynomials given as coefficients ordered highest-degree first.
+fn poly_mul(gf: &Gf256, p: &[u8], q: &[u8]) -> Vec<u8> {
+    let mut result = vec![0u8; p.len() + q.len() - 1];
+    for (j, &pj) in p.iter().enumerate() {
+        if pj == 0 {
+            continue;
+        }
+        for (k, &qk) in q.iter().enumerate() {
+            result[j + k] ^= gf.mul(pj, qk);
+        }
+    }
+    result
+}
+
+/// Evaluates a polynomial (highest-degree first) at `x` using Horner's rule.
+fn poly_eval(gf: &Gf256, poly: &[u8], x: u8) -> u8 {
+    let mut y = poly[0];
+    for &coef in &poly[1..] {
+        y = gf.mul(y, x) ^ coef;
+    }
+    y
+}
+
+/// Builds the RS generator polynomial g(x) = prod_{i=0}^{nsym-1} (x - alpha^i).
+fn generator_poly(gf: &Gf256, nsym: usize) -> Vec<u8> {
+    let mut g = vec![1u8];
+    for i in 0..nsym {
+        g = poly_mul(gf, &g, &[1, gf.pow(2, i)]);
+    }
+    g
+}
+
+/// Encodes one `DATA_LEN`-byte block into a `CODEWORD_LEN`-byte systematic codeword.
+fn encode_block(gf: &Gf256, data: &[u8]) -> Vec<u8> {
+    debug_assert_eq!(data.len(), DATA_LEN);
+
+    let gen = generator_poly(gf, PARITY_LEN);
+    let mut buf = vec![0u8; data.len() + PARITY_LEN];
+    buf[..data.len()].copy_from_slice(data);
+
+    for i in 0..data.len() {
+        let coef = buf[i];
+        if coef != 0 {
+            for (j, &g) in gen.iter().enumerate() {
+                buf[i + j] ^= gf.mul(g, coef);
+            }
+        }
+    }
+
+    let mut codeword = data.to_vec();
+    codeword.extend_from_slice(&buf[data.len()..]);
+    codeword
+}
+
+/// Computes the `nsym` syndromes of `codeword`, evaluating it at alpha^0..alpha^(nsym-1),
+/// prefixed with a leading zero so later steps (Berlekamp-Massey, the error evaluator)
+/// can index one position back without running off the start of the vector.
+/// All-zero syndromes mean the codeword is (as far as RS can tell) uncorrupted.
+fn syndromes(gf: &Gf256, codeword: &[u8], nsym: usize) -> Vec<u8> {
+    let mut synd = vec![0u8; nsym + 1];
+    for i in 0..nsym {
+        synd[i + 1] = poly_eval(gf, codeword, gf.pow(2, i));
+    }
+    synd
+}
+
+/// Finds the error-locator polynomial via the Berlekamp-Massey algorithm.
+/// `synd` carries the leading zero produced by `syndromes`, so real syndromes
+/// start at index `synd_shift`.
+fn find_error_locator(gf: &Gf256, synd: &[u8], nsym: usize) -> Result<Vec<u8>> {
+    let mut err_loc = vec![1u8];
+    let mut old_loc = vec![1u8];
+    let synd_shift = synd.len() - nsym;
+
+    for i in 0..nsym {
+        let k = i + synd_shift;
+        let mut delta = synd[k];
+        for j in 1..err_loc.len() {
+            delta ^= gf.mul(err_loc[err_loc.len() - 1 - j], synd[k - j]);
+        }
+        old_loc.push(0);
+
+        if delta != 0 {
+            if old_loc.len() > err_loc.len() {
+                let new_loc = poly_scale(gf, &old_loc, delta);
+                old_loc = poly_scale(gf, &err_loc, gf.inv(delta));
+                err_loc = new_loc;
+            }
+            let scaled = poly_scale(gf, &old_loc, delta);
+            err_loc = poly_xor(&err_loc, &scaled);
+        }
+    }
+
+    while err_loc.first() == Some(&0) {
+        err_loc.remove(0);
+    }
+
+    let errs = err_loc.len() - 1;
+    if errs * 2 > nsym {
+        return Err(Box::new(RsError::TooManyErrors));
+    }
+
+    Ok(err_loc)
+}
+
+fn poly_scale(gf: &Gf256, poly: &[u8], scalar: u8) -> Vec<u8> {
+    poly.iter().map(|&c| gf.mul(c, scalar)).collect()
+}
+
+/// XORs two polynomials (highest-degree first), right-aligning the shorter one.
+fn poly_xor(p: &[u8], q: &[u8]) -> Vec<u8> {
+    let len = p.len().max(q.len());
+    let mut result = vec![0u8; len];
+    for (i, &c) in p.iter().rev().enumerate() {
+        result[len - 1 - i] ^= c;
+    }
+    for (i, &c) in q.iter().rev().enumerate() {
+        result[len - 1 - i] ^= c;
+    }
+    result
+}
+
+/// Locates error positions via Chien search: each root `alpha^i` of the error
+/// locator corresponds to the byte position `(i - 1) mod codeword_len` (since
+/// `correct_errata`'s `X` values are defined as `alpha^(codeword_len - 1 - position)`).
+fn find_error_positions(gf: &Gf256, err_loc: &[u8], codeword_len: usize) -> Result<Vec<usize>> {
+    let errs = err_loc.len() - 1;
+    let mut positions = Vec::new();
+    for i in 0..codeword_len {
+        if poly_eval(gf, err_loc, gf.pow(2, i)) == 0 {
+            positions.push((i + codeword_len - 1) % codeword_len);
+        }
+    }
+
+    if positions.len() != errs {
+        return Err(Box::new(RsError::ErrorLocatorFailed));
+    }
+
+    Ok(positions)
+}
+
+/// Computes the error-evaluator polynomial Omega(x) = (S(x) * Sigma(x)) mod x^(errs+1).
+fn error_evaluator(gf: &Gf256, synd_poly: &[u8], err_loc: &[u8], errs: usize) -> Vec<u8> {
+    let product = poly_mul(gf, synd_poly, err_loc);
+    let keep = errs + 1;
+    if product.len() <= keep {
+        product
+    } else {
+        product[product.len() - keep..].to_vec()
+    }
+}
+
+/// Applies Forney's algorithm to compute error magnitudes and returns a corrected
+/// copy of `codeword`.
+fn correct_errata(gf: &Gf256, codeword: &[u8], synd: &[u8], err_pos: &[usize]) -> Result<Vec<u8>> {
+    let n = codeword.len();
+    let coef_pos: Vec<usize> = err_pos.iter().map(|&p| n - 1 - p).collect();
+
+    let mut err_loc = vec![1u8];
+    let x: Vec<u8> = coef_pos.iter().map(|&cp| gf.pow(2, cp)).collect();
+    for &xi in &x {
+        err_loc = poly_mul(gf, &err_loc, &[xi, 1]);
+    }
+
+    let synd_rev: Vec<u8> = synd.iter().rev().copied().collect();
+    let err_eval = error_evaluator(gf, &synd_rev, &err_loc, err_pos.len());
+
+    let mut errata = vec![0u8; n];
+    for (i, &xi) in x.iter().enumerate() {
+        let xi_inv = gf.inv(xi);
+
+        let mut denom = 1u8;
+        for (j, &xj) in x.iter().enumerate() {
+            if j != i {
+                denom = gf.mul(denom, 1 ^ gf.mul(xi_inv, xj));
+            }
+        }
+        if denom == 0 {
+            return Err(Box::new(RsError::UncorrectableBlock));
+        }
+
+        let y = gf.mul(xi, poly_eval(gf, &err_eval, xi_inv));
+        errata[err_pos[i]] = gf.div(y, denom);
+    }
+
+    Ok(codeword.iter().zip(errata.iter()).map(|(&c, &e)| c ^ e).collect())
+}
+
+/// Decodes and error-corrects a single `CODEWORD_LEN`-byte codeword, returning its
+/// `DATA_LEN` data bytes.
+fn decode_block(gf: &Gf256, codeword: &[u8]) -> Result<Vec<u8>> {
+    let synd = syndromes(gf, codeword, PARITY_LEN);
+    if synd.iter().all(|&s| s == 0) {
+        return Ok(codeword[..DATA_LEN].to_vec());
+    }
+
+    let err_loc = find_error_locator(gf, &synd, PARITY_LEN)?;
+    let err_pos = find_error_positions(gf, &err_loc, codeword.len())?;
+    let corrected = correct_errata(gf, codeword, &synd, &err_pos)?;
+
+    Ok(corrected[..DATA_LEN].to_vec())
+}
+
+/// Wraps `payload` in Reed-Solomon parity, one `CODEWORD_LEN`-byte codeword per
+/// `DATA_LEN`-byte block, prefixed with a header giving the original length so the
+/// final block's zero padding can be stripped back out on decode.
+pub fn encode(payload: &[u8]) -> Vec<u8> {
+    let gf = Gf256::new();
+    let mut out = Vec::with_capacity(HEADER_LEN + payload.len());
+    out.extend_from_slice(&(payload.len() as u32).to_be_bytes());
+
+    for block in payload.chunks(DATA_LEN) {
+        let mut data = block.to_vec();
+        data.resize(DATA_LEN, 0);
+        out.extend(encode_block(&gf, &data));
+    }
+
+    out
+}
+
+/// Reverses `encode`, correcting up to `PARITY_LEN / 2` byte errors in each block.
+/// If a block's syndromes are all zero, it is returned unmodified without running
+/// the correction pipeline.
+pub fn decode(bytes: &[u8]) -> Result<Vec<u8>> {
+    if bytes.len() < HEADER_LEN {
+        return Err(Box::new(RsError::InputTooShort));
+    }
+
+    let (len_bytes, codewords) = bytes.split_at(HEADER_LEN);
+    let original_len = u32::from_be_bytes(len_bytes.try_into()?) as usize;
+
+    if codewords.len() % CODEWORD_LEN != 0 {
+        return Err(Box::new(RsError::InvalidBlockLength(codewords.len())));
+    }
+
+    let gf = Gf256::new();
+    let mut out = Vec::with_capacity(codewords.len());
+    for block in codewords.chunks(CODEWORD_LEN) {
+        out.extend(decode_block(&gf, block)?);
+    }
+
+    out.truncate(original_len);
+    Ok(out)
+}
+
+/// Errors that can occur while encoding or decoding an ECC-wrapped payload.
+#[derive(Debug)]
+pub enum RsError {
+    InputTooShort,
+    InvalidBlockLength(usize),
+    TooManyErrors,
+    ErrorLocatorFailed,
+    UncorrectableBlock,
+}
+
+impl std::error::Error for RsError {}
+
+impl fmt::Display for RsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RsError::InputTooShort => {
+                write!(f, "At least {} bytes must be supplied to decode an ECC payload", HEADER_LEN)
+            }
+            RsError::InvalidBlockLength(actual) => write!(
+                f,
+                "ECC payload length ({} bytes) is not a multiple of the {}-byte codeword size",
+                actual, CODEWORD_LEN
+            ),
+            RsError::TooManyErrors => {
+                write!(f, "Too many errors in a block to be corrected by Reed-Solomon")
+            }
+            RsError::ErrorLocatorFailed => {
+                write!(f, "Could not find enough roots for the error locator polynomial")
+            }
+            RsError::UncorrectableBlock => write!(f, "Block is uncorrectable"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip_without_corruption() {
+        let message = b"This is where your secret message will be!";
+        let encoded = encode(message);
+        let decoded = decode(&encoded).unwrap();
+        assert_eq!(decoded, message);
+    }
+
+    #[test]
+    fn test_round_trip_with_short_block_padding() {
+        let message = b"x";
+        let encoded = encode(message);
+        let decoded = decode(&encoded).unwrap();
+        assert_eq!(decoded, message);
+    }
+
+    #[test]
+    fn test_corrects_corrupted_bytes() {
+        let message = b"This is where your secret message will be!";
+        let mut encoded = encode(message);
+
+        // Corrupt a handful of parity-protected bytes, well within the 16-byte budget.
+        let block_start = HEADER_LEN;
+        encoded[block_start] ^= 0xFF;
+        encoded[block_start + 10] ^= 0x01;
+        encoded[block_start + 200] ^= 0x80;
+
+        let decoded = decode(&encoded).unwrap();
+        assert_eq!(decoded, message);
+    }
+
+    #[test]
+    fn test_large_message_spans_multiple_blocks() {
+        let message: Vec<u8> = (0..600).map(|i| (i % 256) as u8).collect();
+        let encoded = encode(&message);
+        let decoded = decode(&encoded).unwrap();
+        assert_eq!(decoded, message);
+    }
+
+    #[test]
+    fn test_corrects_single_byte_flip_at_every_position() {
+        let message = b"This is where your secret message will be!";
+        let encoded = encode(message);
+
+        for pos in HEADER_LEN..encoded.len() {
+            let mut corrupted = encoded.clone();
+            corrupted[pos] ^= 0xFF;
+            let decoded = decode(&corrupted).unwrap();
+            assert_eq!(decoded, message, "failed to correct a flipped byte at offset {}", pos);
+        }
+    }
+}
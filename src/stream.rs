@@ -0,0 +1,294 @@
+use std::collections::VecDeque;
+use std::convert::TryFrom;
+use std::fmt;
+use std::io::Read;
+
+use crate::chunk::Chunk;
+use crate::chunk_type::ChunkType;
+use crate::png::Png;
+use crate::Result;
+
+/// Largest chunk data length this decoder will allocate for, per the PNG spec's
+/// rule that a chunk's length field must be less than 2^31. Rejecting anything
+/// larger up front means a single corrupted or malicious length field can't
+/// force a multi-gigabyte allocation before it's even validated.
+const MAX_CHUNK_LENGTH: usize = (1 << 31) - 1;
+
+/// An event produced while scanning a PNG byte stream one chunk at a time.
+/// `Begin`/`End`'s fields aren't read by any current caller (callers match on
+/// `Complete` and ignore the rest), but are kept as part of the event's public
+/// shape for callers that want finer-grained progress.
+#[allow(dead_code)]
+#[derive(Debug)]
+pub enum ChunkEvent {
+    /// The length and type of a chunk have just been read off the stream.
+    Begin { chunk_type: ChunkType, length: u32 },
+    /// A chunk's data and CRC were read and the CRC matched.
+    Complete(Chunk),
+    /// A chunk whose CRC matched has been fully consumed. Not emitted for a
+    /// chunk that fails its CRC check; that chunk yields `StreamError::CrcMismatch`.
+    End { chunk_type: ChunkType },
+}
+
+/// Drives a `Signature -> Length -> Type -> Data -> Crc` state machine over a
+/// `Read` source, yielding one `ChunkEvent` per call to `next_event` without ever
+/// buffering more than a single chunk's data in memory.
+///
+/// Unlike `Png::try_from`, a CRC mismatch does not abort the scan: it is surfaced
+/// as `StreamError::CrcMismatch`, which the caller can catch, log, and recover
+/// from by simply calling `next_event` again (the decoder has already advanced
+/// past the bad chunk by the declared length).
+pub struct StreamDecoder<R: Read> {
+    reader: R,
+    signature_read: bool,
+    pending: VecDeque<ChunkEvent>,
+    position: usize,
+}
+
+impl<R: Read> StreamDecoder<R> {
+    pub fn new(reader: R) -> Self {
+        Self {
+            reader,
+            signature_read: false,
+            pending: VecDeque::new(),
+            position: 0,
+        }
+    }
+
+    /// Number of bytes consumed from the underlying reader so far.
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    /// Reads and returns the next event in the stream, or `None` once the
+    /// stream is exhausted.
+    pub fn next_event(&mut self) -> Result<Option<ChunkEvent>> {
+        if let Some(event) = self.pending.pop_front() {
+            return Ok(Some(event));
+        }
+
+        if !self.signature_read {
+            self.read_signature()?;
+        }
+
+        self.read_chunk()
+    }
+
+    fn read_signature(&mut self) -> Result<()> {
+        let mut signature = [0u8; 8];
+        self.reader
+            .read_exact(&mut signature)
+            .map_err(|_| StreamError::InvalidHeader)?;
+
+        if signature != Png::STANDARD_HEADER {
+            return Err(Box::new(StreamError::InvalidHeader));
+        }
+
+        self.signature_read = true;
+        self.position += signature.len();
+        Ok(())
+    }
+
+    fn read_chunk(&mut self) -> Result<Option<ChunkEvent>> {
+        let mut length_bytes = [0u8; 4];
+        match self.reader.read_exact(&mut length_bytes) {
+            Ok(()) => {}
+            Err(e) if e.kind() == std::io::ErrorKind::UnexpectedEof => return Ok(None),
+            Err(e) => return Err(Box::new(e)),
+        }
+        let length = u32::from_be_bytes(length_bytes) as usize;
+        if length > MAX_CHUNK_LENGTH {
+            return Err(Box::new(StreamError::ChunkTooLarge {
+                length,
+                max: MAX_CHUNK_LENGTH,
+            }));
+        }
+
+        let mut type_bytes = [0u8; 4];
+        self.reader.read_exact(&mut type_bytes)?;
+        let chunk_type = ChunkType::try_from(type_bytes)?;
+
+        let mut data = vec![0u8; length];
+        self.reader.read_exact(&mut data)?;
+
+        let mut crc_bytes = [0u8; 4];
+        self.reader.read_exact(&mut crc_bytes)?;
+        let stored_crc = u32::from_be_bytes(crc_bytes);
+
+        let consumed = Chunk::METADATA_BYTES + length;
+        self.position += consumed;
+
+        let chunk = Chunk::new(chunk_type.clone(), data);
+        let computed_crc = chunk.crc();
+
+        if computed_crc != stored_crc {
+            return Err(Box::new(StreamError::CrcMismatch {
+                chunk_type,
+                expected: stored_crc,
+                actual: computed_crc,
+                recover: consumed,
+            }));
+        }
+
+        self.pending.push_back(ChunkEvent::Complete(chunk));
+        self.pending
+            .push_back(ChunkEvent::End { chunk_type: chunk_type.clone() });
+
+        Ok(Some(ChunkEvent::Begin {
+            chunk_type,
+            length: length as u32,
+        }))
+    }
+}
+
+/// Errors surfaced while streaming a PNG. `CrcMismatch` is recoverable: the
+/// stream has already advanced past the offending chunk by `recover` bytes, so
+/// a `--lenient` caller can simply log it and keep calling `next_event`.
+#[derive(Debug)]
+pub enum StreamError {
+    InvalidHeader,
+    CrcMismatch {
+        chunk_type: ChunkType,
+        expected: u32,
+        actual: u32,
+        recover: usize,
+    },
+    /// A chunk's declared length exceeds `MAX_CHUNK_LENGTH`, so its data was
+    /// never allocated or read. Guards against a corrupted or malicious
+    /// length field forcing a huge allocation.
+    ChunkTooLarge {
+        length: usize,
+        max: usize,
+    },
+}
+
+impl std::error::Error for StreamError {}
+
+impl fmt::Display for StreamError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StreamError::InvalidHeader => write!(f, "Input does not start with the PNG header"),
+            StreamError::CrcMismatch {
+                chunk_type,
+                expected,
+                actual,
+                recover,
+            } => write!(
+                f,
+                "CRC mismatch in chunk {}: expected {} but computed {} ({} bytes consumed)",
+                chunk_type, expected, actual, recover
+            ),
+            StreamError::ChunkTooLarge { length, max } => write!(
+                f,
+                "Chunk declares a length of {} bytes, which exceeds the {}-byte maximum",
+                length, max
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+    use std::str::FromStr;
+
+    fn png_bytes(chunks: &[Chunk]) -> Vec<u8> {
+        Png::STANDARD_HEADER
+            .iter()
+            .chain(chunks.iter().flat_map(|chunk| chunk.as_bytes()).collect::<Vec<u8>>().iter())
+            .copied()
+            .collect()
+    }
+
+    fn chunk(chunk_type: &str, data: &str) -> Chunk {
+        Chunk::new(ChunkType::from_str(chunk_type).unwrap(), data.bytes().collect())
+    }
+
+    #[test]
+    fn test_scans_clean_multi_chunk_stream() {
+        let chunks = vec![
+            chunk("FrSt", "first chunk"),
+            chunk("miDl", "middle chunk"),
+            chunk("LASt", "last chunk"),
+        ];
+        let bytes = png_bytes(&chunks);
+        let mut decoder = StreamDecoder::new(Cursor::new(bytes));
+
+        let mut completed = Vec::new();
+        loop {
+            match decoder.next_event().unwrap() {
+                Some(ChunkEvent::Complete(chunk)) => completed.push(chunk),
+                Some(_) => {}
+                None => break,
+            }
+        }
+
+        assert_eq!(completed.len(), 3);
+        assert_eq!(completed[0].chunk_type().to_string(), "FrSt");
+        assert_eq!(completed[1].chunk_type().to_string(), "miDl");
+        assert_eq!(completed[2].chunk_type().to_string(), "LASt");
+    }
+
+    #[test]
+    fn test_recovers_after_crc_mismatch() {
+        let chunks = vec![chunk("FrSt", "first chunk"), chunk("LASt", "last chunk")];
+        let mut bytes = png_bytes(&chunks);
+
+        // Corrupt the first chunk's CRC (the last 4 bytes of its encoding) without
+        // touching its declared length, so the decoder can still skip past it.
+        let first_chunk_end = Png::STANDARD_HEADER.len() + chunks[0].as_bytes().len();
+        bytes[first_chunk_end - 1] ^= 0xFF;
+
+        let mut decoder = StreamDecoder::new(Cursor::new(bytes));
+
+        let err = decoder.next_event().unwrap_err();
+        assert!(matches!(
+            err.downcast_ref::<StreamError>(),
+            Some(StreamError::CrcMismatch { .. })
+        ));
+
+        let mut completed = Vec::new();
+        loop {
+            match decoder.next_event().unwrap() {
+                Some(ChunkEvent::Complete(chunk)) => completed.push(chunk),
+                Some(_) => {}
+                None => break,
+            }
+        }
+
+        assert_eq!(completed.len(), 1);
+        assert_eq!(completed[0].chunk_type().to_string(), "LASt");
+    }
+
+    #[test]
+    fn test_truncated_input_errors_instead_of_panicking() {
+        let chunks = vec![chunk("FrSt", "first chunk")];
+        let mut bytes = png_bytes(&chunks);
+        bytes.truncate(bytes.len() - 5); // cut off the whole CRC and the last data byte
+
+        let mut decoder = StreamDecoder::new(Cursor::new(bytes));
+        assert!(decoder.next_event().is_err());
+    }
+
+    #[test]
+    fn test_eof_exactly_at_chunk_boundary_ends_cleanly() {
+        let bytes = png_bytes(&[]);
+        let mut decoder = StreamDecoder::new(Cursor::new(bytes));
+        assert!(decoder.next_event().unwrap().is_none());
+    }
+
+    #[test]
+    fn test_rejects_bogus_length_without_allocating() {
+        let mut bytes = Png::STANDARD_HEADER.to_vec();
+        bytes.extend_from_slice(&u32::MAX.to_be_bytes()); // declares ~4.29 GB of data
+        bytes.extend_from_slice(b"FrSt");
+
+        let mut decoder = StreamDecoder::new(Cursor::new(bytes));
+        let err = decoder.next_event().unwrap_err();
+        assert!(matches!(
+            err.downcast_ref::<StreamError>(),
+            Some(StreamError::ChunkTooLarge { .. })
+        ));
+    }
+}